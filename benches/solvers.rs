@@ -3,7 +3,7 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion, Benchmark
 use std::fs::File;
 use std::io;
 use std::io::BufRead;
-use beekeeper::{Puzzle, Solver, NaiveSolver, BitmaskSolver, BitmaskBlockSolver, RadixTrieSolver};
+use beekeeper::{Alphabet, Puzzle, Solver, NaiveSolver, BitmaskSolver, BitmaskBlockSolver, BitmaskTreeSolver, RadixTrieSolver};
 
 
 const WORDS_FILE_PATH: &str = "/usr/share/dict/words";
@@ -22,41 +22,105 @@ fn load_dictionary() -> io::Result<Vec<String>> {
 
 pub fn benchmark_solvers(c: &mut Criterion) {
     let dictionary = load_dictionary().unwrap();
+    let alphabet = Alphabet::from_dictionary(dictionary.iter());
     let naive = NaiveSolver::new(dictionary.clone());
     let trie = RadixTrieSolver::new(dictionary.clone());
-    let bitmask = BitmaskSolver::new(dictionary.clone());
-    let bitmask_block = BitmaskBlockSolver::new(dictionary.clone(), 50);
+    let bitmask = BitmaskSolver::new(&alphabet, dictionary.clone());
+    let bitmask_block = BitmaskBlockSolver::new(&alphabet, dictionary.clone(), 50);
+    let bitmask_tree = BitmaskTreeSolver::new(&alphabet, dictionary.clone());
     let mut group = c.benchmark_group("Bee solvers");
 
     let puzzle = Puzzle{
         center_letter: 'e',
-        outer_letters: ['x', 'p', 'u', 'n', 'i', 'g'],
+        outer_letters: vec!['x', 'p', 'u', 'n', 'i', 'g'],
     };
 
     group.bench_function("naive", |b| b.iter(|| naive.solve(&puzzle)));
     group.bench_function("trie", |b| b.iter(|| trie.solve(&puzzle)));
     group.bench_function("bitmask", |b| b.iter(|| bitmask.solve(&puzzle)));
     group.bench_function("bitmask block", |b| b.iter(|| bitmask_block.solve(&puzzle)));
+    group.bench_function("bitmask tree", |b| b.iter(|| bitmask_tree.solve(&puzzle)));
 
     group.finish();
 }
 
 pub fn benchmark_block_size(c: &mut Criterion) {
     let dictionary = load_dictionary().unwrap();
+    let alphabet = Alphabet::from_dictionary(dictionary.iter());
     let mut group = c.benchmark_group("Block Solvers");
 
     let puzzle = Puzzle{
         center_letter: 'e',
-        outer_letters: ['x', 'p', 'u', 'n', 'i', 'g'],
+        outer_letters: vec!['x', 'p', 'u', 'n', 'i', 'g'],
     };
 
     for size in [1, 2, 5, 7, 9, 10, 12, 14, 16, 18, 20, 30, 40, 50, 60, 75, 82, 100, 200, 500, 1000].iter() {
-        let bitmask_block = BitmaskBlockSolver::new(dictionary.clone(), *size);
+        let bitmask_block = BitmaskBlockSolver::new(&alphabet, dictionary.clone(), *size);
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, _| {
             b.iter(|| bitmask_block.solve(&puzzle));
         });
     }
 }
 
+// Compares the serial and rayon-backed paths across dictionary sizes and
+// thread counts, so the crossover point where parallelism starts winning is
+// visible. Only built with `cargo bench --features parallel`.
+#[cfg(feature = "parallel")]
+pub fn benchmark_parallel_vs_serial(c: &mut Criterion) {
+    let dictionary = load_dictionary().unwrap();
+    let mut group = c.benchmark_group("Serial vs parallel");
+
+    let puzzle = Puzzle {
+        center_letter: 'e',
+        outer_letters: vec!['x', 'p', 'u', 'n', 'i', 'g'],
+    };
+
+    for dict_size in [1_000, 10_000, 100_000].iter() {
+        let subset: Vec<String> = dictionary.iter().take(*dict_size).cloned().collect();
+        let subset_alphabet = Alphabet::from_dictionary(subset.iter());
+        let bitmask = BitmaskSolver::new(&subset_alphabet, subset.clone());
+        let bitmask_block = BitmaskBlockSolver::new(&subset_alphabet, subset, 50);
+
+        group.bench_with_input(
+            BenchmarkId::new("bitmask serial", dict_size),
+            dict_size,
+            |b, _| b.iter(|| bitmask.solve_serial(&puzzle)),
+        );
+
+        for threads in [1, 2, 4, 8].iter() {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(*threads)
+                .build()
+                .unwrap();
+
+            group.bench_with_input(
+                BenchmarkId::new(format!("bitmask parallel ({} threads)", threads), dict_size),
+                dict_size,
+                |b, _| pool.install(|| b.iter(|| bitmask.solve_parallel(&puzzle))),
+            );
+
+            group.bench_with_input(
+                BenchmarkId::new(
+                    format!("bitmask block parallel ({} threads)", threads),
+                    dict_size,
+                ),
+                dict_size,
+                |b, _| pool.install(|| b.iter(|| bitmask_block.solve_parallel(&puzzle))),
+            );
+        }
+
+        group.bench_with_input(
+            BenchmarkId::new("bitmask block serial", dict_size),
+            dict_size,
+            |b, _| b.iter(|| bitmask_block.solve_serial(&puzzle)),
+        );
+    }
+
+    group.finish();
+}
+
+#[cfg(not(feature = "parallel"))]
 criterion_group!(benches, benchmark_block_size);
+#[cfg(feature = "parallel")]
+criterion_group!(benches, benchmark_block_size, benchmark_parallel_vs_serial);
 criterion_main!(benches);
@@ -0,0 +1,263 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::{Alphabet, LetterSet, Puzzle, Solver};
+
+// The classic NYT Spelling Bee uses 7 letters, but the letter count is
+// configurable via `PuzzleGenerator::with_letter_count` for variant puzzles.
+const DEFAULT_LETTER_COUNT: usize = 7;
+
+/// Filters applied when generating puzzles. `min_answers`/`max_answers`
+/// bound the number of words a candidate puzzle must admit (inclusive).
+/// `exclude_s` reproduces the NYT's rule against "s" in a puzzle's letters,
+/// since plurals make an "s" pangram too easy.
+pub struct GeneratorOptions {
+    pub min_answers: Option<usize>,
+    pub max_answers: Option<usize>,
+    pub exclude_s: bool,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> GeneratorOptions {
+        GeneratorOptions {
+            min_answers: None,
+            max_answers: None,
+            exclude_s: false,
+        }
+    }
+}
+
+/// Generates valid Spelling Bee puzzles from a dictionary. A puzzle is
+/// valid only if its letters admit at least one pangram, so
+/// `PuzzleGenerator` indexes every dictionary word with exactly
+/// `letter_count` distinct letters as a "pangram seed"; each seed's letters
+/// are the candidate puzzle's letters, and any one of them may be the
+/// center.
+pub struct PuzzleGenerator {
+    alphabet: Alphabet,
+    letter_count: usize,
+    seeds: Vec<Vec<char>>,
+}
+
+impl PuzzleGenerator {
+    /// Indexes `dictionary` for 7-letter pangram seeds, the classic Spelling
+    /// Bee layout, under `alphabet`. `alphabet` should be the same one
+    /// shared with the solver passed to `generate_all`/`generate_one`. Use
+    /// `with_letter_count` for other sizes.
+    pub fn new(alphabet: &Alphabet, dictionary: Vec<String>) -> PuzzleGenerator {
+        PuzzleGenerator::with_letter_count(alphabet, dictionary, DEFAULT_LETTER_COUNT)
+    }
+
+    /// Indexes `dictionary` for pangram seeds of `letter_count` distinct
+    /// letters, under `alphabet`. Words with more distinct letters are never
+    /// seeds, but may still appear as answers.
+    pub fn with_letter_count(
+        alphabet: &Alphabet,
+        dictionary: Vec<String>,
+        letter_count: usize,
+    ) -> PuzzleGenerator {
+        let mut seeds = Vec::new();
+        for word in dictionary.iter() {
+            if LetterSet::from_word(alphabet, word).popcount() as usize != letter_count {
+                continue;
+            }
+            let mut letters: Vec<char> = word.chars().collect();
+            letters.sort();
+            letters.dedup();
+            if letters.len() != letter_count {
+                continue;
+            }
+            seeds.push(letters);
+        }
+        PuzzleGenerator {
+            alphabet: alphabet.clone(),
+            letter_count: letter_count,
+            seeds: seeds,
+        }
+    }
+
+    /// Returns every puzzle that satisfies `options`: one candidate per
+    /// (pangram seed, center letter) pair, solved with `solver` to apply the
+    /// answer-count filters.
+    pub fn generate_all(&self, solver: &impl Solver, options: &GeneratorOptions) -> Vec<Puzzle> {
+        let mut result = Vec::new();
+        for seed in self.seeds.iter() {
+            if options.exclude_s && seed.contains(&'s') {
+                continue;
+            }
+            for &center in seed.iter() {
+                let puzzle = Puzzle {
+                    center_letter: center,
+                    outer_letters: outer_letters(seed, center),
+                };
+                if self.accepts(solver, &puzzle, options) {
+                    result.push(puzzle);
+                }
+            }
+        }
+        result
+    }
+
+    /// Generates a single random valid puzzle, using a seeded RNG so the
+    /// result is reproducible for a given `rng_seed`. Seeds and center
+    /// letters are tried in shuffled order until one satisfies `options`;
+    /// returns `None` if none of them do.
+    pub fn generate_one(
+        &self,
+        solver: &impl Solver,
+        options: &GeneratorOptions,
+        rng_seed: u64,
+    ) -> Option<Puzzle> {
+        let mut rng = StdRng::seed_from_u64(rng_seed);
+
+        let mut seed_order: Vec<usize> = (0..self.seeds.len()).collect();
+        seed_order.shuffle(&mut rng);
+
+        for seed_index in seed_order {
+            let seed = &self.seeds[seed_index];
+            if options.exclude_s && seed.contains(&'s') {
+                continue;
+            }
+
+            let mut centers = seed.clone();
+            centers.shuffle(&mut rng);
+
+            for center in centers {
+                let puzzle = Puzzle {
+                    center_letter: center,
+                    outer_letters: outer_letters(seed, center),
+                };
+                if self.accepts(solver, &puzzle, options) {
+                    return Some(puzzle);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The alphabet derived from the dictionary this generator was built
+    /// from. Useful for scoring or re-solving generated puzzles.
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    /// The number of distinct letters every generated puzzle has.
+    pub fn letter_count(&self) -> usize {
+        self.letter_count
+    }
+
+    fn accepts(&self, solver: &impl Solver, puzzle: &Puzzle, options: &GeneratorOptions) -> bool {
+        if options.min_answers.is_none() && options.max_answers.is_none() {
+            return true;
+        }
+        let answer_count = solver.solve(puzzle).len();
+        if let Some(min) = options.min_answers {
+            if answer_count < min {
+                return false;
+            }
+        }
+        if let Some(max) = options.max_answers {
+            if answer_count > max {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Splits a seed into its outer letters, in seed order, with `center`
+/// removed.
+fn outer_letters(seed: &[char], center: char) -> Vec<char> {
+    seed.iter().filter(|&&c| c != center).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NaiveSolver;
+
+    // Two 7-letter pangram seeds (one containing 's'), plus a short word
+    // that's too small to ever be a seed itself.
+    fn dictionary() -> Vec<String> {
+        vec![
+            "abcdefg".to_string(),
+            "stuvwxy".to_string(),
+            "ab".to_string(),
+        ]
+    }
+
+    fn alphabet() -> Alphabet {
+        Alphabet::from_dictionary(dictionary().iter())
+    }
+
+    #[test]
+    fn generates_one_puzzle_per_seed_and_center_letter() {
+        let alphabet = alphabet();
+        let generator = PuzzleGenerator::new(&alphabet, dictionary());
+        let solver = NaiveSolver::new(dictionary());
+        let puzzles = generator.generate_all(&solver, &GeneratorOptions::default());
+
+        // Two 7-letter seeds, one puzzle per possible center letter each.
+        assert_eq!(puzzles.len(), 14);
+        assert!(puzzles.iter().all(|p| p.outer_letters.len() == 6));
+    }
+
+    #[test]
+    fn exclude_s_drops_seeds_containing_s() {
+        let alphabet = alphabet();
+        let generator = PuzzleGenerator::new(&alphabet, dictionary());
+        let solver = NaiveSolver::new(dictionary());
+        let options = GeneratorOptions {
+            exclude_s: true,
+            ..Default::default()
+        };
+        let puzzles = generator.generate_all(&solver, &options);
+
+        assert_eq!(puzzles.len(), 7);
+        assert!(puzzles
+            .iter()
+            .all(|p| p.center_letter != 's' && !p.outer_letters.contains(&'s')));
+    }
+
+    #[test]
+    fn min_answers_filters_out_puzzles_below_the_threshold() {
+        let alphabet = alphabet();
+        let generator = PuzzleGenerator::new(&alphabet, dictionary());
+        let solver = NaiveSolver::new(dictionary());
+        let options = GeneratorOptions {
+            min_answers: Some(1000),
+            ..Default::default()
+        };
+
+        assert!(generator.generate_all(&solver, &options).is_empty());
+    }
+
+    #[test]
+    fn max_answers_filters_out_puzzles_above_the_threshold() {
+        let alphabet = alphabet();
+        let generator = PuzzleGenerator::new(&alphabet, dictionary());
+        let solver = NaiveSolver::new(dictionary());
+        let options = GeneratorOptions {
+            max_answers: Some(0),
+            ..Default::default()
+        };
+
+        assert!(generator.generate_all(&solver, &options).is_empty());
+    }
+
+    #[test]
+    fn generate_one_is_reproducible_for_a_fixed_seed() {
+        let alphabet = alphabet();
+        let generator = PuzzleGenerator::new(&alphabet, dictionary());
+        let solver = NaiveSolver::new(dictionary());
+        let options = GeneratorOptions::default();
+
+        let first = generator.generate_one(&solver, &options, 42).unwrap();
+        let second = generator.generate_one(&solver, &options, 42).unwrap();
+
+        assert_eq!(first.center_letter, second.center_letter);
+        assert_eq!(first.outer_letters, second.outer_letters);
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not};
+
+/// Maps the characters observed in a dictionary to bit positions, in
+/// first-seen order. `LetterSet` masks are only meaningful relative to the
+/// `Alphabet` that produced them, so share one `Alphabet` across whatever
+/// needs to agree on what a bit means.
+#[derive(Debug, Clone)]
+pub struct Alphabet {
+    bit_of: HashMap<char, u32>,
+}
+
+impl Alphabet {
+    /// Builds an alphabet from every distinct character appearing in
+    /// `dictionary`, assigning bits in first-seen order. `LetterSet` is
+    /// backed by a `u64`, so past the first 63 characters, the rest share
+    /// bit 63 rather than panicking.
+    pub fn from_dictionary<'a, I>(dictionary: I) -> Alphabet
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        const OVERFLOW_BIT: u32 = 63;
+        let mut bit_of = HashMap::new();
+        let mut next_bit: u32 = 0;
+        for word in dictionary {
+            for c in word.chars() {
+                if bit_of.contains_key(&c) {
+                    continue;
+                }
+                if next_bit < OVERFLOW_BIT {
+                    bit_of.insert(c, next_bit);
+                    next_bit += 1;
+                } else {
+                    bit_of.insert(c, OVERFLOW_BIT);
+                }
+            }
+        }
+        Alphabet { bit_of: bit_of }
+    }
+
+    /// Returns the bit position assigned to `c`, if it was observed while
+    /// building this alphabet.
+    pub fn bit_index(&self, c: char) -> Option<u32> {
+        self.bit_of.get(&c).copied()
+    }
+
+    /// Returns the number of distinct characters in this alphabet.
+    pub fn len(&self) -> usize {
+        self.bit_of.len()
+    }
+}
+
+/// A bitmask over an `Alphabet`: one bit per distinct letter, set if that
+/// letter is present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LetterSet(u64);
+
+impl LetterSet {
+    /// The empty set: no letters present.
+    pub const EMPTY: LetterSet = LetterSet(0);
+
+    /// The set with every bit set. The identity element for AND.
+    pub const ALL: LetterSet = LetterSet(!0);
+
+    /// Returns the single-letter set for `c` under `alphabet`. Characters
+    /// not observed while building `alphabet` map to the empty set.
+    pub fn from_char(alphabet: &Alphabet, c: char) -> LetterSet {
+        match alphabet.bit_index(c) {
+            Some(bit) => LetterSet(1 << bit),
+            None => LetterSet::EMPTY,
+        }
+    }
+
+    /// Returns the set of distinct letters present in `word`, under
+    /// `alphabet`.
+    pub fn from_word(alphabet: &Alphabet, word: &str) -> LetterSet {
+        let mut chars: Vec<char> = word.chars().collect();
+        chars.sort();
+        chars.dedup();
+        chars
+            .iter()
+            .fold(LetterSet::EMPTY, |acc, c| acc | LetterSet::from_char(alphabet, *c))
+    }
+
+    /// Returns true if `c` is one of the letters in this set, under
+    /// `alphabet`.
+    pub fn contains(&self, alphabet: &Alphabet, c: char) -> bool {
+        !(*self & LetterSet::from_char(alphabet, c)).is_empty()
+    }
+
+    /// Returns the number of distinct letters in this set.
+    pub fn popcount(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Returns true if this set has no letters in it.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitAnd for LetterSet {
+    type Output = LetterSet;
+
+    fn bitand(self, rhs: LetterSet) -> LetterSet {
+        LetterSet(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for LetterSet {
+    type Output = LetterSet;
+
+    fn bitor(self, rhs: LetterSet) -> LetterSet {
+        LetterSet(self.0 | rhs.0)
+    }
+}
+
+impl Not for LetterSet {
+    type Output = LetterSet;
+
+    fn not(self) -> LetterSet {
+        LetterSet(!self.0)
+    }
+}
+
+impl BitAndAssign for LetterSet {
+    fn bitand_assign(&mut self, rhs: LetterSet) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitOrAssign for LetterSet {
+    fn bitor_assign(&mut self, rhs: LetterSet) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alphabet() -> Alphabet {
+        Alphabet::from_dictionary(vec!["cat".to_string(), "dog".to_string()].iter())
+    }
+
+    #[test]
+    fn contains_checks_membership_under_the_alphabet() {
+        let alphabet = alphabet();
+        let set = LetterSet::from_char(&alphabet, 'c');
+        assert!(set.contains(&alphabet, 'c'));
+        assert!(!set.contains(&alphabet, 'a'));
+    }
+
+    #[test]
+    fn popcount_counts_distinct_letters() {
+        let alphabet = alphabet();
+        assert_eq!(LetterSet::from_word(&alphabet, "coco").popcount(), 2);
+        assert_eq!(LetterSet::EMPTY.popcount(), 0);
+        assert_eq!(LetterSet::ALL.popcount(), 64);
+    }
+
+    #[test]
+    fn bitand_intersects() {
+        let alphabet = alphabet();
+        let c = LetterSet::from_char(&alphabet, 'c');
+        let a = LetterSet::from_char(&alphabet, 'a');
+        assert!((c & a).is_empty());
+        assert_eq!((c & c), c);
+    }
+
+    #[test]
+    fn bitor_unions() {
+        let alphabet = alphabet();
+        let c = LetterSet::from_char(&alphabet, 'c');
+        let a = LetterSet::from_char(&alphabet, 'a');
+        let both = c | a;
+        assert!(both.contains(&alphabet, 'c'));
+        assert!(both.contains(&alphabet, 'a'));
+        assert_eq!(both.popcount(), 2);
+    }
+
+    #[test]
+    fn not_complements() {
+        let alphabet = alphabet();
+        let c = LetterSet::from_char(&alphabet, 'c');
+        assert!((c & !c).is_empty());
+        assert_eq!(c | !c, LetterSet::ALL);
+    }
+
+    #[test]
+    fn alphabets_past_63_distinct_characters_fold_into_a_shared_overflow_bit() {
+        let words: Vec<String> = (0..70u32)
+            .map(|i| char::from_u32(0x4e00 + i).unwrap().to_string())
+            .collect();
+        let alphabet = Alphabet::from_dictionary(words.iter());
+
+        let first = words[0].chars().next().unwrap();
+        let last_two: Vec<char> = words[68..70].iter().map(|w| w.chars().next().unwrap()).collect();
+
+        assert_ne!(alphabet.bit_index(first), alphabet.bit_index(last_two[0]));
+        assert_eq!(alphabet.bit_index(last_two[0]), alphabet.bit_index(last_two[1]));
+    }
+}
@@ -1,6 +1,13 @@
 use std::collections::HashMap;
 use std::vec::Vec;
 
+mod generator;
+mod letter_set;
+mod scoring;
+pub use generator::{GeneratorOptions, PuzzleGenerator};
+pub use letter_set::{Alphabet, LetterSet};
+pub use scoring::{score_solution, ScoredSolution, ScoredWord};
+
 // Words must be at least 4 characters long to be valid answers.
 const MIN_LENGTH: usize = 4;
 
@@ -8,9 +15,12 @@ const MIN_LENGTH: usize = 4;
 const TYPICAL_RESULT_SIZE: usize = 100;
 
 
+// Puzzle no longer hardcodes 6 outer letters: outer_letters is a Vec so
+// puzzles can use larger (or smaller) alphabets than the classic 7-letter
+// Spelling Bee layout.
 pub struct Puzzle {
     pub center_letter: char,
-    pub outer_letters: [char; 6],
+    pub outer_letters: Vec<char>,
 }
 
 impl Puzzle {
@@ -166,96 +176,106 @@ impl TrieNode {
 }
 
 pub struct BitmaskSolver {
+    alphabet: Alphabet,
     bitmasks: Vec<BitmaskedWord>,
 }
 
 struct BitmaskedWord {
-    mask: u32,
+    mask: LetterSet,
     word: String,
 }
 
 impl BitmaskSolver {
-    pub fn new(dictionary: Vec<String>) -> BitmaskSolver {
+    /// Builds a solver for `dictionary` under `alphabet`. `alphabet` should
+    /// be shared with whatever else (other solvers, a `PuzzleGenerator`)
+    /// needs its masks to mean the same thing as this solver's.
+    pub fn new(alphabet: &Alphabet, dictionary: Vec<String>) -> BitmaskSolver {
         let mut bitmasks = Vec::with_capacity(dictionary.len());
 
         for word in dictionary.iter() {
             if word.len() >= MIN_LENGTH {
                 bitmasks.push(BitmaskedWord {
-                    mask: BitmaskSolver::bitmask_word(word),
+                    mask: LetterSet::from_word(alphabet, word),
                     word: word.to_string(),
                 });
             }
         }
 
-        BitmaskSolver { bitmasks: bitmasks }
-    }
-
-    fn bitmask_letter(letter: &char) -> u32 {
-        match letter {
-            'a' => 1 << 0,
-            'b' => 1 << 1,
-            'c' => 1 << 2,
-            'd' => 1 << 3,
-            'e' => 1 << 4,
-            'f' => 1 << 5,
-            'g' => 1 << 6,
-            'h' => 1 << 7,
-            'i' => 1 << 8,
-            'j' => 1 << 9,
-            'k' => 1 << 10,
-            'l' => 1 << 11,
-            'm' => 1 << 12,
-            'n' => 1 << 13,
-            'o' => 1 << 14,
-            'p' => 1 << 15,
-            'q' => 1 << 16,
-            'r' => 1 << 17,
-            's' => 1 << 18,
-            't' => 1 << 19,
-            'u' => 1 << 20,
-            'v' => 1 << 21,
-            'w' => 1 << 22,
-            'x' => 1 << 23,
-            'y' => 1 << 24,
-            'z' => 1 << 25,
-            _ => 1 << 26,
+        BitmaskSolver {
+            alphabet: alphabet.clone(),
+            bitmasks: bitmasks,
         }
     }
+}
 
-    fn bitmask_word(word: &str) -> u32 {
-        let mut chars: Vec<char> = word.chars().collect();
-        chars.sort();
-        chars.dedup();
-        let mut mask: u32 = 0;
-        for c in chars.iter() {
-            mask |= BitmaskSolver::bitmask_letter(c);
-        }
-        mask
+/// Computes the puzzle's own letter mask (center letter plus every outer
+/// letter) under `alphabet`.
+pub(crate) fn puzzle_letter_mask(alphabet: &Alphabet, puzzle: &Puzzle) -> LetterSet {
+    let mut mask = LetterSet::from_char(alphabet, puzzle.center_letter);
+    for letter in puzzle.outer_letters.iter() {
+        mask |= LetterSet::from_char(alphabet, *letter);
     }
+    mask
 }
 
-impl Solver for BitmaskSolver {
-    fn solve(&self, puzzle: &Puzzle) -> Vec<String> {
-        let center_letter_mask = BitmaskSolver::bitmask_letter(&puzzle.center_letter);
+/// Computes the forbidden-letter mask for a puzzle under `alphabet`: every
+/// letter which must *not* appear in a valid word. This is the complement
+/// of the puzzle's own letters (center plus outer).
+fn forbidden_letter_mask(alphabet: &Alphabet, puzzle: &Puzzle) -> LetterSet {
+    !puzzle_letter_mask(alphabet, puzzle)
+}
 
-        // forbidden_letter_mask has 1 for every letter which must *not* be
-        // used. We compute it by ORing together all the allowed words, and then
-        // inverting.
-        let mut forbidden_letter_mask: u32 = center_letter_mask;
-        for letter in puzzle.outer_letters.iter() {
-            forbidden_letter_mask |= BitmaskSolver::bitmask_letter(letter)
-        }
-        forbidden_letter_mask = !forbidden_letter_mask;
+impl BitmaskSolver {
+    /// Single-threaded scan over every word's mask. This is what `solve`
+    /// calls unless the "parallel" feature is enabled.
+    pub fn solve_serial(&self, puzzle: &Puzzle) -> Vec<String> {
+        let center_letter_mask = LetterSet::from_char(&self.alphabet, puzzle.center_letter);
+        let forbidden_letter_mask = forbidden_letter_mask(&self.alphabet, puzzle);
 
         let mut result: Vec<String> = Vec::with_capacity(TYPICAL_RESULT_SIZE);
         for mask in self.bitmasks.iter() {
-            if (mask.mask & center_letter_mask != 0) && (mask.mask & forbidden_letter_mask == 0) {
+            if !(mask.mask & center_letter_mask).is_empty()
+                && (mask.mask & forbidden_letter_mask).is_empty()
+            {
                 result.push(mask.word.to_string());
             }
         }
 
         result
     }
+
+    // Each word is tested against the puzzle's masks independently, so the
+    // whole vector can be scanned with rayon instead of a single thread.
+    // Order isn't preserved, but callers already sort before comparing
+    // solutions.
+    #[cfg(feature = "parallel")]
+    pub fn solve_parallel(&self, puzzle: &Puzzle) -> Vec<String> {
+        use rayon::prelude::*;
+
+        let center_letter_mask = LetterSet::from_char(&self.alphabet, puzzle.center_letter);
+        let forbidden_letter_mask = forbidden_letter_mask(&self.alphabet, puzzle);
+
+        self.bitmasks
+            .par_iter()
+            .filter(|mask| {
+                !(mask.mask & center_letter_mask).is_empty()
+                    && (mask.mask & forbidden_letter_mask).is_empty()
+            })
+            .map(|mask| mask.word.clone())
+            .collect()
+    }
+}
+
+impl Solver for BitmaskSolver {
+    #[cfg(not(feature = "parallel"))]
+    fn solve(&self, puzzle: &Puzzle) -> Vec<String> {
+        self.solve_serial(puzzle)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn solve(&self, puzzle: &Puzzle) -> Vec<String> {
+        self.solve_parallel(puzzle)
+    }
 }
 
 /*
@@ -275,11 +295,15 @@ It's not immediately clear what the block size should be, so it is left
 configurable for now while I do some experimentation.
 */
 pub struct BitmaskBlockSolver {
+    alphabet: Alphabet,
     blocks: Vec<BitmaskBlock>,
 }
 
 impl BitmaskBlockSolver {
-    pub fn new(dictionary: Vec<String>, chunk_size: usize) -> BitmaskBlockSolver {
+    /// Builds a solver for `dictionary` under `alphabet`. `alphabet` should
+    /// be shared with whatever else (other solvers, a `PuzzleGenerator`)
+    /// needs its masks to mean the same thing as this solver's.
+    pub fn new(alphabet: &Alphabet, dictionary: Vec<String>, chunk_size: usize) -> BitmaskBlockSolver {
         let mut blocks = Vec::with_capacity(dictionary.len() / chunk_size + 1);
         let mut sorted: Vec<String> = dictionary
             .iter()
@@ -288,24 +312,22 @@ impl BitmaskBlockSolver {
             .collect();
         sorted.sort();
         for chunk in sorted.chunks(chunk_size) {
-            blocks.push(BitmaskBlock::new(chunk));
+            blocks.push(BitmaskBlock::new(alphabet, chunk));
+        }
+        BitmaskBlockSolver {
+            alphabet: alphabet.clone(),
+            blocks: blocks,
         }
-        BitmaskBlockSolver { blocks: blocks }
     }
 }
 
-impl Solver for BitmaskBlockSolver {
-    fn solve(&self, puzzle: &Puzzle) -> Vec<String> {
-        let center_letter_mask = BitmaskSolver::bitmask_letter(&puzzle.center_letter);
-
-        // forbidden_letter_mask has 1 for every letter which must *not* be
-        // used. We compute it by ORing together all the allowed words, and then
-        // inverting.
-        let mut forbidden_letter_mask: u32 = center_letter_mask;
-        for letter in puzzle.outer_letters.iter() {
-            forbidden_letter_mask |= BitmaskSolver::bitmask_letter(letter)
-        }
-        forbidden_letter_mask = !forbidden_letter_mask;
+impl BitmaskBlockSolver {
+    /// Single-threaded scan over blocks, pruning each one against the
+    /// puzzle's masks before scanning its words. This is what `solve` calls
+    /// unless the "parallel" feature is enabled.
+    pub fn solve_serial(&self, puzzle: &Puzzle) -> Vec<String> {
+        let center_letter_mask = LetterSet::from_char(&self.alphabet, puzzle.center_letter);
+        let forbidden_letter_mask = forbidden_letter_mask(&self.alphabet, puzzle);
 
         let mut result: Vec<String> = Vec::with_capacity(TYPICAL_RESULT_SIZE);
 
@@ -316,26 +338,59 @@ impl Solver for BitmaskBlockSolver {
         }
         result
     }
+
+    // Blocks are independent: each one is pruned against the puzzle's masks
+    // on its own, so with the "parallel" feature enabled we hand the whole
+    // vector of blocks to rayon and let it test pruning masks (and scan
+    // surviving blocks' words) across threads.
+    #[cfg(feature = "parallel")]
+    pub fn solve_parallel(&self, puzzle: &Puzzle) -> Vec<String> {
+        use rayon::prelude::*;
+
+        let center_letter_mask = LetterSet::from_char(&self.alphabet, puzzle.center_letter);
+        let forbidden_letter_mask = forbidden_letter_mask(&self.alphabet, puzzle);
+
+        self.blocks
+            .par_iter()
+            .flat_map(|block| {
+                block
+                    .matches(center_letter_mask, forbidden_letter_mask)
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+impl Solver for BitmaskBlockSolver {
+    #[cfg(not(feature = "parallel"))]
+    fn solve(&self, puzzle: &Puzzle) -> Vec<String> {
+        self.solve_serial(puzzle)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn solve(&self, puzzle: &Puzzle) -> Vec<String> {
+        self.solve_parallel(puzzle)
+    }
 }
 
 struct BitmaskBlock {
     // Mask encoding the characters present in all words in the block.
-    common_chars_mask: u32,
+    common_chars_mask: LetterSet,
     // Mask encoding the characters present in no words in the block.
-    missing_chars_mask: u32,
+    missing_chars_mask: LetterSet,
     // The words present in the block.
     words: Vec<BitmaskedWord>,
 }
 
 impl BitmaskBlock {
-    fn new(words: &[String]) -> BitmaskBlock {
-        let mut common_chars_mask: u32 = !0;
-        let mut missing_chars_mask: u32 = 0;
+    fn new(alphabet: &Alphabet, words: &[String]) -> BitmaskBlock {
+        let mut common_chars_mask: LetterSet = LetterSet::ALL;
+        let mut missing_chars_mask: LetterSet = LetterSet::EMPTY;
         let mut masked_words = Vec::with_capacity(words.len());
 
         for w in words.iter() {
             let masked_word = BitmaskedWord {
-                mask: BitmaskSolver::bitmask_word(&w),
+                mask: LetterSet::from_word(alphabet, w),
                 word: w.to_string(),
             };
             missing_chars_mask |= masked_word.mask;
@@ -352,16 +407,22 @@ impl BitmaskBlock {
 
     /// Returns the list of all words that match, if there are any matches. If
     /// there aren't any, then returns None.
-    fn matches(&self, center_letter_mask: u32, forbidden_letter_mask: u32) -> Option<Vec<String>> {
-        if (self.common_chars_mask & forbidden_letter_mask) != 0 {
+    fn matches(
+        &self,
+        center_letter_mask: LetterSet,
+        forbidden_letter_mask: LetterSet,
+    ) -> Option<Vec<String>> {
+        if !(self.common_chars_mask & forbidden_letter_mask).is_empty() {
             return None;
         }
-        if (self.missing_chars_mask & center_letter_mask) == 0 {
+        if (self.missing_chars_mask & center_letter_mask).is_empty() {
             return None;
         }
         let mut result: Vec<String> = Vec::with_capacity(self.words.len());
         for w in self.words.iter() {
-            if (w.mask & center_letter_mask != 0) && (w.mask & forbidden_letter_mask == 0) {
+            if !(w.mask & center_letter_mask).is_empty()
+                && (w.mask & forbidden_letter_mask).is_empty()
+            {
                 result.push(w.word.to_string());
             }
         }
@@ -372,3 +433,188 @@ impl BitmaskBlock {
         }
     }
 }
+
+/*
+BitmaskTreeSolver generalizes BitmaskBlockSolver from one level of blocks to a
+balanced tree of arbitrary depth. Words are lexicographically sorted and
+grouped into leaves of `leaf_size` words each; leaves are then grouped into
+internal nodes `branching_factor` at a time, repeating until a single root
+remains. Every node - leaf or internal - carries the same pair of bitmasks as
+a block did: `common` (the AND of everything below it) and `present` (the OR
+of everything below it).
+
+Solving is a top-down traversal that prunes a whole subtree as soon as either
+mask proves it can't contain a match, so only the leaves (and the words
+inside them) that survive both tests are ever scanned. With one level this
+collapses to the same behavior as BitmaskBlockSolver; with many it turns an
+O(blocks) scan into roughly O(log n + matches).
+*/
+const DEFAULT_LEAF_SIZE: usize = 50;
+const DEFAULT_BRANCHING_FACTOR: usize = 16;
+
+pub struct BitmaskTreeSolver {
+    alphabet: Alphabet,
+    root: TreeNode,
+}
+
+impl BitmaskTreeSolver {
+    /// Builds a solver for `dictionary` under `alphabet`, using the default
+    /// leaf size and branching factor. `alphabet` should be shared with
+    /// whatever else (other solvers, a `PuzzleGenerator`) needs its masks to
+    /// mean the same thing as this solver's.
+    pub fn new(alphabet: &Alphabet, dictionary: Vec<String>) -> BitmaskTreeSolver {
+        BitmaskTreeSolver::with_shape(alphabet, dictionary, DEFAULT_LEAF_SIZE, DEFAULT_BRANCHING_FACTOR)
+    }
+
+    pub fn with_shape(
+        alphabet: &Alphabet,
+        dictionary: Vec<String>,
+        leaf_size: usize,
+        branching_factor: usize,
+    ) -> BitmaskTreeSolver {
+        let mut sorted: Vec<String> = dictionary
+            .iter()
+            .filter(|w| w.len() >= MIN_LENGTH)
+            .cloned()
+            .collect();
+        sorted.sort();
+
+        let mut level: Vec<TreeNode> = sorted
+            .chunks(leaf_size.max(1))
+            .map(|chunk| TreeNode::leaf(alphabet, chunk))
+            .collect();
+        if level.is_empty() {
+            level.push(TreeNode::leaf(alphabet, &[]));
+        }
+        while level.len() > 1 {
+            level = group_into_internal_nodes(level, branching_factor.max(2));
+        }
+
+        BitmaskTreeSolver {
+            alphabet: alphabet.clone(),
+            root: level.into_iter().next().unwrap(),
+        }
+    }
+}
+
+impl Solver for BitmaskTreeSolver {
+    fn solve(&self, puzzle: &Puzzle) -> Vec<String> {
+        let center_letter_mask = LetterSet::from_char(&self.alphabet, puzzle.center_letter);
+        let forbidden_letter_mask = forbidden_letter_mask(&self.alphabet, puzzle);
+
+        let mut result: Vec<String> = Vec::with_capacity(TYPICAL_RESULT_SIZE);
+        self.root
+            .collect_matches(center_letter_mask, forbidden_letter_mask, &mut result);
+        result
+    }
+}
+
+/// Consumes `nodes` and regroups them into internal nodes of at most
+/// `group_size` children each, one level up the tree.
+fn group_into_internal_nodes(mut nodes: Vec<TreeNode>, group_size: usize) -> Vec<TreeNode> {
+    let mut groups = Vec::with_capacity(nodes.len() / group_size + 1);
+    while !nodes.is_empty() {
+        let rest = if nodes.len() > group_size {
+            nodes.split_off(group_size)
+        } else {
+            Vec::new()
+        };
+        groups.push(TreeNode::internal(nodes));
+        nodes = rest;
+    }
+    groups
+}
+
+enum TreeNode {
+    Leaf {
+        common: LetterSet,
+        present: LetterSet,
+        words: Vec<BitmaskedWord>,
+    },
+    Internal {
+        common: LetterSet,
+        present: LetterSet,
+        children: Vec<TreeNode>,
+    },
+}
+
+impl TreeNode {
+    fn common(&self) -> LetterSet {
+        match self {
+            TreeNode::Leaf { common, .. } => *common,
+            TreeNode::Internal { common, .. } => *common,
+        }
+    }
+
+    fn present(&self) -> LetterSet {
+        match self {
+            TreeNode::Leaf { present, .. } => *present,
+            TreeNode::Internal { present, .. } => *present,
+        }
+    }
+
+    fn leaf(alphabet: &Alphabet, words: &[String]) -> TreeNode {
+        let mut common = LetterSet::ALL;
+        let mut present = LetterSet::EMPTY;
+        let mut masked_words = Vec::with_capacity(words.len());
+
+        for w in words.iter() {
+            let mask = LetterSet::from_word(alphabet, w);
+            common &= mask;
+            present |= mask;
+            masked_words.push(BitmaskedWord {
+                mask: mask,
+                word: w.to_string(),
+            });
+        }
+
+        TreeNode::Leaf {
+            common: common,
+            present: present,
+            words: masked_words,
+        }
+    }
+
+    fn internal(children: Vec<TreeNode>) -> TreeNode {
+        let mut common = LetterSet::ALL;
+        let mut present = LetterSet::EMPTY;
+        for child in children.iter() {
+            common &= child.common();
+            present |= child.present();
+        }
+
+        TreeNode::Internal {
+            common: common,
+            present: present,
+            children: children,
+        }
+    }
+
+    /// Prunes this subtree against the puzzle's masks, appending any
+    /// matching words to `out`.
+    fn collect_matches(&self, center_letter_mask: LetterSet, forbidden_letter_mask: LetterSet, out: &mut Vec<String>) {
+        if !(self.common() & forbidden_letter_mask).is_empty() {
+            return;
+        }
+        if (self.present() & center_letter_mask).is_empty() {
+            return;
+        }
+
+        match self {
+            TreeNode::Leaf { words, .. } => {
+                for w in words.iter() {
+                    if !(w.mask & center_letter_mask).is_empty()
+                        && (w.mask & forbidden_letter_mask).is_empty()
+                    {
+                        out.push(w.word.to_string());
+                    }
+                }
+            }
+            TreeNode::Internal { children, .. } => {
+                for child in children.iter() {
+                    child.collect_matches(center_letter_mask, forbidden_letter_mask, out);
+                }
+            }
+        }
+    }
+}
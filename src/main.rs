@@ -2,7 +2,7 @@ use std::fs::{read_to_string, File};
 use std::io;
 use std::io::BufRead;
 
-use beekeeper::{Puzzle, Solver, NaiveSolver, BitmaskSolver, BitmaskBlockSolver, RadixTrieSolver};
+use beekeeper::{Alphabet, Puzzle, Solver, NaiveSolver, BitmaskSolver, BitmaskBlockSolver, BitmaskTreeSolver, RadixTrieSolver};
 
 const WORDS_FILE_PATH: &str = "/usr/share/dict/words";
 
@@ -30,21 +30,24 @@ fn load_puzzle_from_file(path: &str) -> io::Result<Puzzle> {
     let chars: Vec<char> = raw.chars().collect();
     let p = Puzzle {
         center_letter: chars[0],
-        outer_letters: [chars[1], chars[2], chars[3], chars[4], chars[5], chars[6]],
+        outer_letters: vec![chars[1], chars[2], chars[3], chars[4], chars[5], chars[6]],
     };
     Ok(p)
 }
 
 fn main() {
     let dictionary = load_dictionary().unwrap();
+    let alphabet = Alphabet::from_dictionary(dictionary.iter());
     println!("building native");
     let naive = NaiveSolver::new(dictionary.clone());
     println!("building radix");
     let trie = RadixTrieSolver::new(dictionary.clone());
     println!("building bitmask");
-    let bitmask = BitmaskSolver::new(dictionary.clone());
+    let bitmask = BitmaskSolver::new(&alphabet, dictionary.clone());
     println!("building blockwise bitmask (50-size blocks)");
-    let bitmask_block = BitmaskBlockSolver::new(dictionary.clone(), 50);
+    let bitmask_block = BitmaskBlockSolver::new(&alphabet, dictionary.clone(), 50);
+    println!("building tree bitmask");
+    let bitmask_tree = BitmaskTreeSolver::new(&alphabet, dictionary.clone());
 
     let puzzle = load_puzzle_from_file("puzzle.txt").unwrap();
     println!("Puzzle: {}", puzzle.to_string());
@@ -53,6 +56,7 @@ fn main() {
     benchmark_solver("trie", &trie, &puzzle);
     benchmark_solver("bitmask", &bitmask, &puzzle);
     benchmark_solver("bitmask-block", &bitmask_block, &puzzle);
+    benchmark_solver("bitmask-tree", &bitmask_tree, &puzzle);
 }
 
 fn benchmark_solver(label: &str, solver: &impl Solver, puzzle: &Puzzle) {
@@ -72,7 +76,7 @@ fn test_trie_solver() {
     let trie = RadixTrieSolver::new(dictionary.clone());
     let puzzle = Puzzle {
         center_letter: 'a',
-        outer_letters: ['b', 'c', 'd', 'e', 'f', 'g'],
+        outer_letters: vec!['b', 'c', 'd', 'e', 'f', 'g'],
     };
 
     let mut naive_solution = naive.solve(&puzzle);
@@ -86,11 +90,12 @@ fn test_trie_solver() {
 #[test]
 fn test_bitmask_solver() {
     let dictionary = load_dictionary().unwrap();
+    let alphabet = Alphabet::from_dictionary(dictionary.iter());
     let naive = NaiveSolver::new(dictionary.clone());
-    let bitmask = BitmaskSolver::new(dictionary.clone());
+    let bitmask = BitmaskSolver::new(&alphabet, dictionary.clone());
     let puzzle = Puzzle {
         center_letter: 'a',
-        outer_letters: ['b', 'c', 'd', 'e', 'f', 'g'],
+        outer_letters: vec!['b', 'c', 'd', 'e', 'f', 'g'],
     };
 
     let mut naive_solution = naive.solve(&puzzle);
@@ -104,11 +109,12 @@ fn test_bitmask_solver() {
 #[test]
 fn test_blockbitmask_solver() {
     let dictionary = load_dictionary().unwrap();
+    let alphabet = Alphabet::from_dictionary(dictionary.iter());
     let naive = NaiveSolver::new(dictionary.clone());
-    let block_bitmask = BitmaskBlockSolver::new(dictionary.clone(), 50);
+    let block_bitmask = BitmaskBlockSolver::new(&alphabet, dictionary.clone(), 50);
     let puzzle = Puzzle {
         center_letter: 'a',
-        outer_letters: ['b', 'c', 'd', 'e', 'f', 'g'],
+        outer_letters: vec!['b', 'c', 'd', 'e', 'f', 'g'],
     };
 
     let mut naive_solution = naive.solve(&puzzle);
@@ -118,3 +124,47 @@ fn test_blockbitmask_solver() {
     block_bitmask_solution.sort();
     assert!(naive_solution == block_bitmask_solution);
 }
+
+#[test]
+fn test_bitmasktree_solver() {
+    let dictionary = load_dictionary().unwrap();
+    let alphabet = Alphabet::from_dictionary(dictionary.iter());
+    let naive = NaiveSolver::new(dictionary.clone());
+    let tree_bitmask = BitmaskTreeSolver::with_shape(&alphabet, dictionary.clone(), 5, 3);
+    let puzzle = Puzzle {
+        center_letter: 'a',
+        outer_letters: vec!['b', 'c', 'd', 'e', 'f', 'g'],
+    };
+
+    let mut naive_solution = naive.solve(&puzzle);
+    let mut tree_bitmask_solution = tree_bitmask.solve(&puzzle);
+
+    naive_solution.sort();
+    tree_bitmask_solution.sort();
+    assert!(naive_solution == tree_bitmask_solution);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_matches_serial() {
+    let dictionary = load_dictionary().unwrap();
+    let alphabet = Alphabet::from_dictionary(dictionary.iter());
+    let bitmask = BitmaskSolver::new(&alphabet, dictionary.clone());
+    let block_bitmask = BitmaskBlockSolver::new(&alphabet, dictionary.clone(), 50);
+    let puzzle = Puzzle {
+        center_letter: 'a',
+        outer_letters: vec!['b', 'c', 'd', 'e', 'f', 'g'],
+    };
+
+    let mut bitmask_serial = bitmask.solve_serial(&puzzle);
+    let mut bitmask_parallel = bitmask.solve_parallel(&puzzle);
+    bitmask_serial.sort();
+    bitmask_parallel.sort();
+    assert!(bitmask_serial == bitmask_parallel);
+
+    let mut block_bitmask_serial = block_bitmask.solve_serial(&puzzle);
+    let mut block_bitmask_parallel = block_bitmask.solve_parallel(&puzzle);
+    block_bitmask_serial.sort();
+    block_bitmask_parallel.sort();
+    assert!(block_bitmask_serial == block_bitmask_parallel);
+}
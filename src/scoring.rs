@@ -0,0 +1,104 @@
+use crate::{puzzle_letter_mask, Alphabet, LetterSet, Puzzle};
+
+/// A single scored answer: the word itself, the points it's worth, and
+/// whether it's a pangram (uses every one of the puzzle's letters).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoredWord {
+    pub word: String,
+    pub points: u32,
+    pub is_pangram: bool,
+}
+
+/// A solver's solution, scored against a puzzle: every answer with its
+/// points, plus the total achievable score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoredSolution {
+    pub words: Vec<ScoredWord>,
+    pub total_points: u32,
+}
+
+/// Scores `word` against `puzzle` using NYT rules: 4-letter words are worth
+/// 1 point, longer words score their length in points, and a pangram - a
+/// word using every one of the puzzle's letters - earns a +7 bonus on top
+/// of that.
+fn score_word(alphabet: &Alphabet, puzzle_mask: LetterSet, word: &str) -> ScoredWord {
+    let is_pangram = LetterSet::from_word(alphabet, word) == puzzle_mask;
+
+    let mut points = if word.len() == 4 { 1 } else { word.len() as u32 };
+    if is_pangram {
+        points += 7;
+    }
+
+    ScoredWord {
+        word: word.to_string(),
+        points: points,
+        is_pangram: is_pangram,
+    }
+}
+
+/// Converts a flat solution (as returned by `Solver::solve`) into a
+/// `ScoredSolution`, scoring every word against `puzzle` under `alphabet`.
+/// `alphabet` must be the same one the solver that produced `solution` used.
+pub fn score_solution(alphabet: &Alphabet, puzzle: &Puzzle, solution: &[String]) -> ScoredSolution {
+    let mask = puzzle_letter_mask(alphabet, puzzle);
+
+    let words: Vec<ScoredWord> = solution
+        .iter()
+        .map(|w| score_word(alphabet, mask, w))
+        .collect();
+    let total_points = words.iter().map(|w| w.points).sum();
+
+    ScoredSolution {
+        words: words,
+        total_points: total_points,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // "bedgah" uses every one of the puzzle's letters (a pangram); "bade"
+    // and "added" each use a strict subset.
+    fn puzzle_and_alphabet() -> (Alphabet, Puzzle) {
+        let dictionary = vec![
+            "bade".to_string(),
+            "added".to_string(),
+            "bedgah".to_string(),
+        ];
+        let alphabet = Alphabet::from_dictionary(dictionary.iter());
+        let puzzle = Puzzle {
+            center_letter: 'a',
+            outer_letters: vec!['b', 'd', 'e', 'g', 'h'],
+        };
+        (alphabet, puzzle)
+    }
+
+    #[test]
+    fn four_letter_word_is_worth_one_point() {
+        let (alphabet, puzzle) = puzzle_and_alphabet();
+        let scored = score_solution(&alphabet, &puzzle, &["bade".to_string()]);
+
+        assert_eq!(scored.words[0].points, 1);
+        assert!(!scored.words[0].is_pangram);
+    }
+
+    #[test]
+    fn longer_non_pangram_word_scores_its_length() {
+        let (alphabet, puzzle) = puzzle_and_alphabet();
+        let scored = score_solution(&alphabet, &puzzle, &["added".to_string()]);
+
+        assert_eq!(scored.words[0].points, 5);
+        assert!(!scored.words[0].is_pangram);
+    }
+
+    #[test]
+    fn pangram_adds_a_seven_point_bonus_on_top_of_length() {
+        let (alphabet, puzzle) = puzzle_and_alphabet();
+        let scored = score_solution(&alphabet, &puzzle, &["bedgah".to_string()]);
+
+        assert!(scored.words[0].is_pangram);
+        assert_eq!(scored.words[0].points, 6 + 7);
+        assert_eq!(scored.total_points, 13);
+    }
+}